@@ -1,4 +1,6 @@
 use std::io::stdout;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
 
 use color_eyre::Result;
 use crossterm::{
@@ -7,7 +9,7 @@ use crossterm::{
 };
 use ratatui::{
     DefaultTerminal, Frame,
-    crossterm::event::{self, Event, KeyCode, KeyEventKind},
+    crossterm::event::{self, Event, KeyCode, KeyEventKind, MouseButton, MouseEvent, MouseEventKind},
     prelude::*,
     style::{Color, Style},
     symbols::Marker,
@@ -17,22 +19,93 @@ use ratatui::{
     },
 };
 
+const TICK_RATE: Duration = Duration::from_millis(33);
+const GRAVITY: f64 = 40.0;
+const BOUNCE_DAMPING: f64 = 0.3;
+const SETTLE_VELOCITY: f64 = 1.0;
+const BOARD_COLS: f64 = 7.0;
+const BOARD_ROWS: f64 = 6.0;
+const X_MARGIN: f64 = 9.0;
+const Y_MARGIN: f64 = 2.0;
+
+const MARKERS: [Marker; 5] = [
+    Marker::Dot,
+    Marker::Braille,
+    Marker::Block,
+    Marker::HalfBlock,
+    Marker::Bar,
+];
+
+/// Braille packs 2x4 subcells per cell, so it needs a bigger radius to read as round.
+fn radius_for_marker(marker: Marker) -> f64 {
+    match marker {
+        Marker::Braille => 0.3,
+        Marker::HalfBlock => 0.2,
+        Marker::Dot | Marker::Block | Marker::Bar => 0.15,
+    }
+}
+
+fn color_name(color: Color) -> &'static str {
+    match color {
+        Color::Yellow => "Yellow",
+        Color::Red => "Red",
+        _ => "?",
+    }
+}
+
+const SAVE_FILE: &str = "connect4.save";
+
+/// One byte per cell, in `placements` order, preceded by the side to move
+/// and the game state. Compact and dead simple to round-trip by hand.
+const SAVE_LEN: usize = 2 + 7 * 6;
+
 fn main() -> Result<()> {
     color_eyre::install()?;
     stdout().execute(EnableMouseCapture)?;
     let terminal = ratatui::init();
-    let app_result = App::new().run(terminal);
+    let app_result = App::load(PathBuf::from(SAVE_FILE)).run(terminal);
     ratatui::restore();
     stdout().execute(DisableMouseCapture)?;
     app_result
 }
 
+struct FallingChip {
+    col: usize,
+    row: usize,
+    vy: f64,
+    landing_y: f64,
+    color: Color,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct MoveRecord {
+    col: usize,
+    row: usize,
+    color: Color,
+}
+
+const WIN_DIRECTIONS: [(i32, i32); 4] = [(1, 0), (0, 1), (1, 1), (1, -1)];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum GameState {
+    Playing,
+    Won(Color),
+    Draw,
+}
+
 struct App {
     exit: bool,
     marker: Marker,
+    radius: f64,
     color: Color,
     chip_circle: Circle,
     placements: [[Option<Circle>; 6]; 7],
+    falling: Option<FallingChip>,
+    canvas_rect: Rect,
+    undo_stack: Vec<MoveRecord>,
+    redo_stack: Vec<MoveRecord>,
+    game_state: GameState,
+    file_name: Option<PathBuf>,
 }
 
 impl App {
@@ -42,43 +115,177 @@ impl App {
         Self {
             exit: false,
             marker: Marker::HalfBlock,
+            radius: radius_for_marker(Marker::HalfBlock),
             color: Color::Yellow,
             chip_circle: Circle {
                 x: 0.5,
                 y: 6.5,
-                radius: 0.2,
+                radius: radius_for_marker(Marker::HalfBlock),
                 color: Color::Yellow,
             },
             placements: grid,
+            falling: None,
+            canvas_rect: Rect::default(),
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            game_state: GameState::Playing,
+            file_name: None,
+        }
+    }
+
+    /// `path` becomes the target for the next `save_game` even if loading fails.
+    fn load(path: PathBuf) -> Self {
+        let mut app = Self::new();
+        app.file_name = Some(path.clone());
+
+        if let Ok(bytes) = std::fs::read(&path) {
+            app.apply_save(&bytes);
+        }
+
+        app
+    }
+
+    fn save_game(&mut self) {
+        let path = self
+            .file_name
+            .clone()
+            .unwrap_or_else(|| PathBuf::from(SAVE_FILE));
+
+        let _ = std::fs::write(&path, self.to_save_bytes());
+        self.file_name = Some(path);
+    }
+
+    fn to_save_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(SAVE_LEN);
+        bytes.push(match self.color {
+            Color::Yellow => 0,
+            _ => 1,
+        });
+        bytes.push(match self.game_state {
+            GameState::Playing => 0,
+            GameState::Won(Color::Yellow) => 1,
+            GameState::Won(_) => 2,
+            GameState::Draw => 3,
+        });
+        for col in &self.placements {
+            for cell in col {
+                bytes.push(match cell {
+                    None => 0,
+                    Some(chip) if chip.color == Color::Yellow => 1,
+                    Some(_) => 2,
+                });
+            }
+        }
+        bytes
+    }
+
+    /// Parses a save file produced by `to_save_bytes` and, if every byte is
+    /// in range, applies it to `self`. Leaves `self` untouched (an empty
+    /// board) on any malformed or out-of-range input rather than panicking.
+    fn apply_save(&mut self, bytes: &[u8]) -> bool {
+        if bytes.len() != SAVE_LEN {
+            return false;
+        }
+
+        let color = match bytes[0] {
+            0 => Color::Yellow,
+            1 => Color::Red,
+            _ => return false,
+        };
+        let game_state = match bytes[1] {
+            0 => GameState::Playing,
+            1 => GameState::Won(Color::Yellow),
+            2 => GameState::Won(Color::Red),
+            3 => GameState::Draw,
+            _ => return false,
+        };
+
+        let mut placements: [[Option<Circle>; 6]; 7] =
+            std::array::from_fn(|_| std::array::from_fn(|_| None));
+        for (col, cells) in placements.iter_mut().enumerate() {
+            for (row, cell) in cells.iter_mut().enumerate() {
+                let color = match bytes[2 + col * 6 + row] {
+                    0 => None,
+                    1 => Some(Color::Yellow),
+                    2 => Some(Color::Red),
+                    _ => return false,
+                };
+                *cell = color.map(|color| Circle {
+                    x: col as f64 + 0.5,
+                    y: row as f64 + 0.5,
+                    radius: self.radius,
+                    color,
+                });
+            }
         }
+
+        self.color = color;
+        self.game_state = game_state;
+        self.placements = placements;
+        self.chip_circle.color = color;
+        true
     }
 
     pub fn run(mut self, mut terminal: DefaultTerminal) -> Result<()> {
+        let mut last_tick = Instant::now();
         while !self.exit {
             terminal.draw(|frame| self.draw(frame))?;
-            match event::read()? {
-                Event::Key(key) => self.handle_key_press(key),
-                Event::Mouse(_) => (),
-                _ => (),
+
+            let timeout = TICK_RATE.saturating_sub(last_tick.elapsed());
+            if event::poll(timeout)? {
+                match event::read()? {
+                    Event::Key(key) => self.handle_key_press(key),
+                    Event::Mouse(mouse) => self.handle_mouse(mouse),
+                    _ => (),
+                }
+            }
+
+            if last_tick.elapsed() >= TICK_RATE {
+                self.on_tick();
+                last_tick = Instant::now();
             }
         }
         Ok(())
     }
 
+    fn on_tick(&mut self) {
+        let Some(falling) = self.falling.as_mut() else {
+            return;
+        };
+
+        let dt = TICK_RATE.as_secs_f64();
+        falling.vy += GRAVITY * dt;
+        self.chip_circle.y -= falling.vy * dt;
+
+        if self.chip_circle.y <= falling.landing_y {
+            self.chip_circle.y = falling.landing_y;
+            falling.vy = -falling.vy * BOUNCE_DAMPING;
+            if falling.vy.abs() < SETTLE_VELOCITY {
+                self.settle_falling_chip();
+            }
+        }
+    }
+
     fn handle_key_press(&mut self, key: event::KeyEvent) {
         if key.kind != KeyEventKind::Press {
             return;
         }
         match key.code {
             KeyCode::Char('q') => self.exit = true,
+            KeyCode::Char('n') => self.reset_game(),
+            KeyCode::Char('m') => self.cycle_marker(),
+            KeyCode::Char('s') => self.save_game(),
+            KeyCode::Char('u') => self.undo_last_move(),
+            KeyCode::Char('r') => self.redo_last_move(),
+            _ if self.game_state != GameState::Playing => {}
             KeyCode::Char('c') => self.color = Color::Red,
             KeyCode::Right => {
-                if self.chip_circle.x < 6.5 {
+                if self.falling.is_none() && self.chip_circle.x < 6.5 {
                     self.chip_circle.x += 1.0;
                 }
             }
             KeyCode::Left => {
-                if self.chip_circle.x > 0.5 {
+                if self.falling.is_none() && self.chip_circle.x > 0.5 {
                     self.chip_circle.x -= 1.0;
                 }
             }
@@ -87,37 +294,88 @@ impl App {
         }
     }
 
-    fn draw(&self, frame: &mut Frame) {
+    /// Moves the drop cursor to the column under the cursor and, on a left
+    /// click, drops a chip there exactly as `Enter` would.
+    fn handle_mouse(&mut self, mouse: MouseEvent) {
+        if self.falling.is_some() || self.game_state != GameState::Playing {
+            return;
+        }
+
+        let Some(col) = self.column_from_mouse(mouse.column, mouse.row) else {
+            return;
+        };
+
+        match mouse.kind {
+            MouseEventKind::Moved | MouseEventKind::Drag(_) => {
+                self.chip_circle.x = col as f64 + 0.5;
+            }
+            MouseEventKind::Down(MouseButton::Left) => {
+                self.chip_circle.x = col as f64 + 0.5;
+                self.add_to_placements();
+            }
+            _ => {}
+        }
+    }
+
+    /// Inverts the snapped canvas geometry to map a terminal click back onto
+    /// a logical board column, or `None` if the click missed the grid.
+    fn column_from_mouse(&self, column: u16, row: u16) -> Option<usize> {
+        if self.canvas_rect.width == 0 || self.canvas_rect.height == 0 {
+            return None;
+        }
+        if column < self.canvas_rect.x
+            || column >= self.canvas_rect.x + self.canvas_rect.width
+            || row < self.canvas_rect.y
+            || row >= self.canvas_rect.y + self.canvas_rect.height
+        {
+            return None;
+        }
+
+        let rel_x = (column - self.canvas_rect.x) as f64;
+        let logical_x =
+            -X_MARGIN + (rel_x / self.canvas_rect.width as f64) * (BOARD_COLS + 2.0 * X_MARGIN);
+
+        if !(0.0..BOARD_COLS).contains(&logical_x) {
+            return None;
+        }
+
+        Some((logical_x as usize).min(6))
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let title = match self.game_state {
+            GameState::Playing => "Connect4".to_string(),
+            GameState::Won(color) => {
+                format!("Connect4 - {} wins! ('n' for new game)", color_name(color))
+            }
+            GameState::Draw => "Connect4 - Draw! ('n' for new game)".to_string(),
+        };
         let block = Block::bordered()
             .border_style(Style::new().fg(self.color))
-            .title("Connect4");
+            .title(title);
         frame.render_widget(block.clone(), frame.area());
         let visual_ratio = 7.0 / 6.0;
         let cell_ratio = visual_ratio / 0.18;
         let center_frame = self.aspect_fit_center(block.inner(frame.area()), 7, 6, cell_ratio);
+        self.canvas_rect = center_frame;
         frame.render_widget(self.c4_canvas(), center_frame);
     }
 
     fn c4_canvas(&self) -> impl Widget {
-        const COLS: f64 = 7.0;
-        const ROWS: f64 = 6.0;
-
-        let x_margin = 9.0;
-        let y_margin = 2.0;
         Canvas::default()
             .marker(self.marker)
-            .x_bounds([-x_margin, COLS + x_margin])
-            .y_bounds([-y_margin, ROWS + y_margin])
+            .x_bounds([-X_MARGIN, BOARD_COLS + X_MARGIN])
+            .y_bounds([-Y_MARGIN, BOARD_ROWS + Y_MARGIN])
             .paint(move |ctx| {
                 ctx.draw(&Rectangle {
                     x: 0.0,
                     y: 0.0,
-                    width: COLS,
-                    height: ROWS,
+                    width: BOARD_COLS,
+                    height: BOARD_ROWS,
                     color: self.color,
                 });
 
-                for x in 0..=COLS as i32 {
+                for x in 0..=BOARD_COLS as i32 {
                     ctx.draw(&Line {
                         x1: x as f64,
                         y1: 0.0,
@@ -127,7 +385,7 @@ impl App {
                     });
                 }
 
-                for y in 0..=ROWS as i32 {
+                for y in 0..=BOARD_ROWS as i32 {
                     ctx.draw(&Line {
                         x1: 0.0,
                         y1: y as f64,
@@ -186,35 +444,174 @@ impl App {
         Rect::new(x, y, snapped_w, snapped_h)
     }
 
+    /// Commits to `placements` only once the drop settles, via `on_tick`.
     fn add_to_placements(&mut self) {
+        if self.falling.is_some() {
+            return;
+        }
+
         let selected_col = self.chip_circle.x as usize;
-        for (i, chip) in self.placements[selected_col].iter_mut().enumerate() {
-            if chip.is_none() {
-                match i {
-                    0 => self.chip_circle.y = 0.5,
-                    1 => self.chip_circle.y = 1.5,
-                    2 => self.chip_circle.y = 2.5,
-                    3 => self.chip_circle.y = 3.5,
-                    4 => self.chip_circle.y = 4.5,
-                    5 => self.chip_circle.y = 5.5,
-                    _ => break,
-                }
+        let landing_row = self.placements[selected_col]
+            .iter()
+            .position(|chip| chip.is_none());
 
-                chip.replace(self.chip_circle.clone());
-                match self.color {
-                    Color::Yellow => self.color = Color::Red,
-                    Color::Red => self.color = Color::Yellow,
-                    _ => {}
-                }
+        let Some(row) = landing_row else {
+            return;
+        };
 
-                self.chip_circle = Circle {
-                    x: 0.5,
-                    y: 6.5,
-                    radius: 0.2,
-                    color: self.color,
-                };
-                break;
+        self.chip_circle.color = self.color;
+        self.falling = Some(FallingChip {
+            col: selected_col,
+            row,
+            vy: 0.0,
+            landing_y: row as f64 + 0.5,
+            color: self.color,
+        });
+    }
+
+    fn settle_falling_chip(&mut self) {
+        let Some(falling) = self.falling.take() else {
+            return;
+        };
+
+        self.place_chip(falling.col, falling.row, falling.color);
+
+        if self.check_win(falling.col, falling.row, falling.color) {
+            self.game_state = GameState::Won(falling.color);
+        } else if self.is_board_full() {
+            self.game_state = GameState::Draw;
+        }
+
+        self.color = match self.color {
+            Color::Yellow => Color::Red,
+            Color::Red => Color::Yellow,
+            other => other,
+        };
+
+        self.chip_circle = Circle {
+            x: 0.5,
+            y: 6.5,
+            radius: self.radius,
+            color: self.color,
+        };
+    }
+
+    fn check_win(&self, col: usize, row: usize, color: Color) -> bool {
+        WIN_DIRECTIONS.iter().any(|&(dx, dy)| {
+            1 + self.count_direction(col, row, dx, dy, color)
+                + self.count_direction(col, row, -dx, -dy, color)
+                >= 4
+        })
+    }
+
+    fn count_direction(&self, col: usize, row: usize, dx: i32, dy: i32, color: Color) -> u32 {
+        let mut count = 0;
+        let mut c = col as i32 + dx;
+        let mut r = row as i32 + dy;
+
+        while (0..7).contains(&c) && (0..6).contains(&r) {
+            match &self.placements[c as usize][r as usize] {
+                Some(chip) if chip.color == color => count += 1,
+                _ => break,
             }
+            c += dx;
+            r += dy;
+        }
+
+        count
+    }
+
+    fn is_board_full(&self) -> bool {
+        self.placements
+            .iter()
+            .all(|col| col.iter().all(Option::is_some))
+    }
+
+    /// Also rescales chips already placed, not just future ones.
+    fn cycle_marker(&mut self) {
+        let next = MARKERS
+            .iter()
+            .position(|&m| m == self.marker)
+            .map_or(0, |i| (i + 1) % MARKERS.len());
+        self.marker = MARKERS[next];
+        self.radius = radius_for_marker(self.marker);
+
+        self.chip_circle.radius = self.radius;
+        for col in self.placements.iter_mut() {
+            for chip in col.iter_mut().flatten() {
+                chip.radius = self.radius;
+            }
+        }
+    }
+
+    fn reset_game(&mut self) {
+        self.placements = std::array::from_fn(|_| std::array::from_fn(|_| None));
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+        self.falling = None;
+        self.color = Color::Yellow;
+        self.chip_circle = Circle {
+            x: 0.5,
+            y: 6.5,
+            radius: self.radius,
+            color: Color::Yellow,
+        };
+        self.game_state = GameState::Playing;
+    }
+
+    /// Recording a move here clears the redo stack, same as any editor undo.
+    fn place_chip(&mut self, col: usize, row: usize, color: Color) {
+        self.placements[col][row] = Some(Circle {
+            x: col as f64 + 0.5,
+            y: row as f64 + 0.5,
+            radius: self.radius,
+            color,
+        });
+        self.undo_stack.push(MoveRecord { col, row, color });
+        self.redo_stack.clear();
+    }
+
+    fn undo_last_move(&mut self) {
+        if self.falling.is_some() {
+            return;
+        }
+        let Some(record) = self.undo_stack.pop() else {
+            return;
+        };
+
+        self.placements[record.col][record.row] = None;
+        self.color = record.color;
+        self.redo_stack.push(record);
+        self.game_state = GameState::Playing;
+    }
+
+    fn redo_last_move(&mut self) {
+        if self.falling.is_some() {
+            return;
+        }
+        let Some(record) = self.redo_stack.pop() else {
+            return;
+        };
+
+        self.placements[record.col][record.row] = Some(Circle {
+            x: record.col as f64 + 0.5,
+            y: record.row as f64 + 0.5,
+            radius: self.radius,
+            color: record.color,
+        });
+        self.undo_stack.push(record);
+        self.color = match record.color {
+            Color::Yellow => Color::Red,
+            Color::Red => Color::Yellow,
+            other => other,
+        };
+
+        if self.check_win(record.col, record.row, record.color) {
+            self.game_state = GameState::Won(record.color);
+        } else if self.is_board_full() {
+            self.game_state = GameState::Draw;
+        } else {
+            self.game_state = GameState::Playing;
         }
     }
 }